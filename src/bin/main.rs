@@ -2,8 +2,9 @@ use raytracer::*;
 
 use rand::distributions::{Distribution, Uniform};
 use rand::rngs::StdRng;
-use rand::{Rng, SeedableRng};
+use rand::{Rng, RngCore, SeedableRng};
 
+use image::{Rgb, RgbImage};
 use indicatif::ProgressBar;
 use indicatif::ProgressStyle;
 use rayon::prelude::*;
@@ -21,8 +22,15 @@ fn main() -> std::io::Result<()> {
     let max_depth = 50;
 
     // world
+    let scene_seed: u64 = 0xC0FFEE;
+    let mut scene_rng = StdRng::seed_from_u64(scene_seed);
+
     let items_in_scene = 11;
-    let world = random_scene(items_in_scene);
+    let world = BvhNode::new(
+        random_scene(items_in_scene, &mut scene_rng),
+        &mut scene_rng,
+    );
+    let background = Color::new(0.7, 0.8, 1.0);
 
     // camera
     let lookfrom = Point::new(13.0, 2.0, 3.0);
@@ -40,11 +48,12 @@ fn main() -> std::io::Result<()> {
         image.aspect_ratio,
         aperture,
         dist_to_focus,
+        0.0,
+        1.0,
     );
 
     // rng
     let distribution = Uniform::new(-1.0, 1.0);
-    let rng = StdRng::from_entropy();
 
     // render
     let denominator_u = image.img_width as f64 - 1.0;
@@ -69,13 +78,16 @@ fn main() -> std::io::Result<()> {
                     let u = (i as f64) / denominator_u;
                     let v = (j as f64) / denominator_v;
 
+                    let pixel_seed = scene_seed ^ ((j as u64) << 32 | i);
+                    let mut rng = StdRng::seed_from_u64(pixel_seed);
+
                     let mut pixel =
                         (0..samples_per_pixel).fold(Color::from_element(0.0), |acc, _| {
-                            let u_extra = distribution.sample(&mut rng.clone()) / denominator_u;
-                            let v_extra = distribution.sample(&mut rng.clone()) / denominator_v;
+                            let u_extra = distribution.sample(&mut rng) / denominator_u;
+                            let v_extra = distribution.sample(&mut rng) / denominator_v;
 
-                            let ray = camera.get_ray(u + u_extra, v + v_extra);
-                            acc + ray_color(&ray, &world, max_depth)
+                            let ray = camera.get_ray(u + u_extra, v + v_extra, &mut rng);
+                            acc + ray_color(&ray, &world, background, max_depth, &mut rng)
                         });
 
                     pixel.apply(|x| {
@@ -94,13 +106,54 @@ fn main() -> std::io::Result<()> {
     progress.println(format!("{:?}", progress.elapsed()));
 
     // io
-    let file = OpenOptions::new()
-        .write(true)
-        .create(true)
-        .open("image.ppm")?;
+    write_image(&image, &scene, "image.png")
+}
+
+/// Writes the rendered `scene` buffer to `path`, dispatching on its extension: `.png` is
+/// encoded via the `image` crate, anything else falls back to a raw `P3` PPM.
+fn write_image(image: &ImageSettings, scene: &[Vec<Point>], path: &str) -> std::io::Result<()> {
+    let is_png = std::path::Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        == Some("png");
+
+    if is_png {
+        write_png(image, scene, path)
+    } else {
+        write_ppm(image, scene, path)
+    }
+}
+
+fn pixel_channel(value: f64) -> u8 {
+    value.clamp(0.0, 255.0) as u8
+}
 
-    let mut writer =
-        BufWriter::with_capacity(image.num_pixels() as usize * samples_per_pixel, file);
+fn write_png(image: &ImageSettings, scene: &[Vec<Point>], path: &str) -> std::io::Result<()> {
+    let mut buffer = RgbImage::new(image.img_width as u32, image.img_height as u32);
+
+    for (j, row) in scene.iter().enumerate() {
+        for (i, pixel) in row.iter().enumerate() {
+            buffer.put_pixel(
+                i as u32,
+                j as u32,
+                Rgb([
+                    pixel_channel(pixel.x),
+                    pixel_channel(pixel.y),
+                    pixel_channel(pixel.z),
+                ]),
+            );
+        }
+    }
+
+    buffer
+        .save(path)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))
+}
+
+fn write_ppm(image: &ImageSettings, scene: &[Vec<Point>], path: &str) -> std::io::Result<()> {
+    let file = OpenOptions::new().write(true).create(true).open(path)?;
+
+    let mut writer = BufWriter::with_capacity(image.num_pixels() as usize * 3, file);
 
     write!(
         writer,
@@ -113,20 +166,26 @@ fn main() -> std::io::Result<()> {
             writeln!(
                 writer,
                 "{} {} {}",
-                pixel.x as i32, pixel.y as i32, pixel.z as i32
+                pixel_channel(pixel.x),
+                pixel_channel(pixel.y),
+                pixel_channel(pixel.z)
             )?;
         }
     }
-    writer.flush()?;
 
-    Ok(())
+    writer.flush()
 }
 
-fn random_scene(size: isize) -> HittableList {
+fn random_scene(size: isize, rng: &mut dyn RngCore) -> HittableList {
     let num_spheres: usize = (4 + (2 * size).pow(2)).try_into().unwrap();
     let mut world = HittableList::with_capacity(num_spheres);
 
-    let ground_material = Arc::new(Lambertian::new(Color::from_element(0.5)));
+    let ground_texture = Arc::new(CheckerTexture::new(
+        Arc::new(SolidColor::new(Color::from_element(0.2))),
+        Arc::new(SolidColor::new(Color::from_element(0.9))),
+        10.0,
+    ));
+    let ground_material = Arc::new(Lambertian::new(ground_texture));
     world.add(Arc::new(Sphere::new(
         Point::new(0.0, -1000.0, 0.0),
         1000.0,
@@ -135,15 +194,14 @@ fn random_scene(size: isize) -> HittableList {
 
     let distribution = Uniform::new(-1.0, 1.0);
     let metal_albedo_distribution = Uniform::new(0.5, 1.0);
-    let mut rng = rand::thread_rng();
 
     let origin_reference = Point::new(4.0, 0.2, 0.0);
 
     for a in -size..=size {
         for b in -size..=size {
-            let choose_material = distribution.sample(&mut rng);
-            let random_x = distribution.sample(&mut rng);
-            let random_y = distribution.sample(&mut rng);
+            let choose_material = distribution.sample(rng);
+            let random_x = distribution.sample(rng);
+            let random_y = distribution.sample(rng);
 
             let origin = Point::new(
                 (a as f64) + 0.9 * random_x,
@@ -156,13 +214,24 @@ fn random_scene(size: isize) -> HittableList {
 
                 if choose_material < 0.8 {
                     // diffuse
-                    let albedo = Color::from_distribution(&distribution, &mut rng)
-                        .component_mul(&Color::from_distribution(&distribution, &mut rng));
-
-                    sphere_material = Arc::new(Lambertian::new(albedo));
+                    let albedo = Color::from_distribution(&distribution, rng)
+                        .component_mul(&Color::from_distribution(&distribution, rng));
+
+                    sphere_material = Arc::new(Lambertian::from_color(albedo));
+
+                    let center1 = origin + Vector::new(0.0, rng.gen_range(0.0..0.5), 0.0);
+                    world.add(Arc::new(MovingSphere::new(
+                        origin,
+                        center1,
+                        0.0,
+                        1.0,
+                        0.2,
+                        sphere_material,
+                    )));
+                    continue;
                 } else if choose_material < 0.95 {
                     // metal
-                    let albedo = Color::from_distribution(&metal_albedo_distribution, &mut rng);
+                    let albedo = Color::from_distribution(&metal_albedo_distribution, rng);
                     let fuzz = rng.gen_range(0.0..0.5);
 
                     sphere_material = Arc::new(Metal::new(albedo, fuzz));
@@ -183,7 +252,7 @@ fn random_scene(size: isize) -> HittableList {
         material1,
     )));
 
-    let material2 = Arc::new(Lambertian::new(Color::new(0.4, 0.2, 0.1)));
+    let material2 = Arc::new(Lambertian::from_color(Color::new(0.4, 0.2, 0.1)));
     world.add(Arc::new(Sphere::new(
         Point::new(-4.0, 1.0, 0.0),
         1.0,
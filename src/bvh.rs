@@ -0,0 +1,80 @@
+use crate::aabb::Aabb;
+use crate::{HitRecord, Hittable, HittableList, Ray};
+
+use rand::{Rng, RngCore};
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+pub struct BvhNode {
+    left: Arc<dyn Hittable>,
+    right: Arc<dyn Hittable>,
+    bounding_box: Aabb,
+}
+
+impl BvhNode {
+    pub fn new(list: HittableList, rng: &mut dyn RngCore) -> Self {
+        Self::from_slice(&mut list.into_vec(), rng)
+    }
+
+    fn from_slice(objects: &mut [Arc<dyn Hittable>], rng: &mut dyn RngCore) -> Self {
+        assert!(
+            !objects.is_empty(),
+            "BvhNode::from_slice called with an empty slice"
+        );
+
+        let axis = rng.gen_range(0..3);
+        let box_min = |item: &Arc<dyn Hittable>| {
+            item.bounding_box()
+                .expect("no bounding box in BvhNode constructor")
+                .min[axis]
+        };
+
+        objects.sort_by(|a, b| box_min(a).partial_cmp(&box_min(b)).unwrap_or(Ordering::Equal));
+
+        let (left, right): (Arc<dyn Hittable>, Arc<dyn Hittable>) = match objects.len() {
+            1 => (Arc::clone(&objects[0]), Arc::clone(&objects[0])),
+            2 => (Arc::clone(&objects[0]), Arc::clone(&objects[1])),
+            len => {
+                let mid = len / 2;
+                let (left_slice, right_slice) = objects.split_at_mut(mid);
+
+                (
+                    Arc::new(Self::from_slice(left_slice, rng)),
+                    Arc::new(Self::from_slice(right_slice, rng)),
+                )
+            }
+        };
+
+        let left_box = left
+            .bounding_box()
+            .expect("no bounding box in BvhNode constructor");
+        let right_box = right
+            .bounding_box()
+            .expect("no bounding box in BvhNode constructor");
+        let bounding_box = Aabb::surrounding_box(&left_box, &right_box);
+
+        Self {
+            left,
+            right,
+            bounding_box,
+        }
+    }
+}
+
+impl Hittable for BvhNode {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        if !self.bounding_box.hit(ray, t_min, t_max) {
+            return None;
+        }
+
+        let left_hit = self.left.hit(ray, t_min, t_max);
+        let closest_so_far = left_hit.as_ref().map_or(t_max, |hit_record| hit_record.t);
+        let right_hit = self.right.hit(ray, t_min, closest_so_far);
+
+        right_hit.or(left_hit)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(self.bounding_box)
+    }
+}
@@ -1,3 +1,4 @@
+use crate::aabb::Aabb;
 use crate::util::{Point, Vector};
 use crate::{Material, Ray};
 use std::marker::{Send, Sync};
@@ -7,17 +8,22 @@ pub struct HitRecord {
     pub point: Point,
     pub normal: Vector,
     pub material: Arc<dyn Material>,
-    t: f64,
+    pub(crate) t: f64,
     pub front_face: bool,
+    pub u: f64,
+    pub v: f64,
 }
 
 impl HitRecord {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         point: Point,
         outward_normal: &Vector,
         material: Arc<dyn Material>,
         t: f64,
         ray: &Ray,
+        u: f64,
+        v: f64,
     ) -> Self {
         let front_face = ray.direction.dot(outward_normal) < 0.0;
 
@@ -32,12 +38,16 @@ impl HitRecord {
             material,
             t,
             front_face,
+            u,
+            v,
         }
     }
 }
 
 pub trait Hittable: Sync + Send {
     fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord>;
+
+    fn bounding_box(&self) -> Option<Aabb>;
 }
 
 pub struct HittableList {
@@ -54,6 +64,10 @@ impl HittableList {
     pub fn add(&mut self, item: Arc<dyn Hittable>) {
         self.items.push(item);
     }
+
+    pub fn into_vec(self) -> Vec<Arc<dyn Hittable>> {
+        self.items
+    }
 }
 
 impl Hittable for HittableList {
@@ -70,4 +84,19 @@ impl Hittable for HittableList {
 
         result
     }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let mut result: Option<Aabb> = None;
+
+        for item in &self.items {
+            let item_box = item.bounding_box()?;
+
+            result = Some(match result {
+                Some(existing_box) => Aabb::surrounding_box(&existing_box, &item_box),
+                None => item_box,
+            });
+        }
+
+        result
+    }
 }
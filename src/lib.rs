@@ -1,27 +1,33 @@
 #![warn(clippy::all)]
 
-mod util;
+mod aabb;
+mod bvh;
 mod hittable;
 mod material;
 mod ray;
 mod shapes;
+mod texture;
+mod util;
 
+pub use crate::aabb::Aabb;
+pub use crate::bvh::BvhNode;
 pub use crate::camera::Camera;
 pub use crate::hittable::{HitRecord, Hittable, HittableList};
 pub use crate::material::Material;
-pub use crate::material::{Dielectric, Lambertian, Metal};
+pub use crate::material::{Dielectric, DiffuseLight, Lambertian, Metal};
 pub use crate::ray::Ray;
-pub use crate::shapes::Sphere;
+pub use crate::shapes::{Cuboid, MovingSphere, Plane, Rect, Sphere};
+pub use crate::texture::{CheckerTexture, SolidColor, Texture};
 pub use crate::util::*;
 
 use rand::distributions::Uniform;
+use rand::RngCore;
 
-fn random_point_in_unit_sphere() -> Point {
+fn random_point_in_unit_sphere(rng: &mut dyn RngCore) -> Point {
     let distribution = Uniform::new(-1.0, 1.0);
-    let mut rng = rand::thread_rng();
 
     loop {
-        let p = Point::from_distribution(&distribution, &mut rng);
+        let p = Point::from_distribution(&distribution, rng);
 
         if p.norm_squared() < 1.0 {
             return p;
@@ -29,12 +35,12 @@ fn random_point_in_unit_sphere() -> Point {
     }
 }
 
-fn random_unit_vector() -> Point {
-    random_point_in_unit_sphere().normalize()
+fn random_unit_vector(rng: &mut dyn RngCore) -> Point {
+    random_point_in_unit_sphere(rng).normalize()
 }
 
-fn random_point_in_hemisphere(normal: &Vector) -> Point {
-    let p = random_point_in_unit_sphere();
+fn random_point_in_hemisphere(normal: &Vector, rng: &mut dyn RngCore) -> Point {
+    let p = random_point_in_unit_sphere(rng);
 
     if p.dot(normal) > 0.0 {
         p
@@ -43,12 +49,11 @@ fn random_point_in_hemisphere(normal: &Vector) -> Point {
     }
 }
 
-fn random_point_in_unit_disk() -> Point {
+fn random_point_in_unit_disk(rng: &mut dyn RngCore) -> Point {
     let distribution = Uniform::new(-1.0, 1.0);
-    let mut rng = rand::thread_rng();
 
     loop {
-        let mut p = Point::from_distribution(&distribution, &mut rng);
+        let mut p = Point::from_distribution(&distribution, rng);
         p[2] = 0.0;
 
         if p.norm_squared() < 1.0 {
@@ -57,24 +62,34 @@ fn random_point_in_unit_disk() -> Point {
     }
 }
 
-pub fn ray_color(ray: &Ray, world: &HittableList, recursion_depth: usize) -> Color {
+pub fn ray_color(
+    ray: &Ray,
+    world: &dyn Hittable,
+    background: Color,
+    recursion_depth: usize,
+    rng: &mut dyn RngCore,
+) -> Color {
     if recursion_depth == 0 {
         // if we exceed the depth, return no light
-        Color::from_element(0.0)
-    } else if let Some(hit_record) = world.hit(ray, 0.001, f64::INFINITY) {
-        hit_record.material.scatter(ray, &hit_record).map_or(
-            Color::from_element(0.0),
-            |(ref scattered_ray, ref attenuation)| {
-                let mut ray = ray_color(scattered_ray, world, recursion_depth - 1);
-                ray.component_mul_assign(attenuation);
-                ray
-            },
-        )
-    } else {
-        // background color
-        let unit_direction = ray.direction.normalize();
-        let t = 0.5 * (unit_direction.y + 1.0);
-        (1.0 - t) * Color::from_element(1.0) + t * Color::new(0.5, 0.7, 1.0)
+        return Color::from_element(0.0);
+    }
+
+    let hit_record = match world.hit(ray, 0.001, f64::INFINITY) {
+        Some(hit_record) => hit_record,
+        None => return background,
+    };
+
+    let emitted = hit_record.material.emitted();
+
+    match hit_record.material.scatter(ray, &hit_record, rng) {
+        Some((ref scattered_ray, ref attenuation)) => {
+            let mut scattered_color =
+                ray_color(scattered_ray, world, background, recursion_depth - 1, rng);
+            scattered_color.component_mul_assign(attenuation);
+
+            emitted + scattered_color
+        }
+        None => emitted,
     }
 }
 
@@ -83,6 +98,9 @@ mod camera {
     use crate::util::{Point, Vector};
     use crate::Ray;
 
+    use rand::distributions::{Distribution, Uniform};
+    use rand::RngCore;
+
     pub struct Camera {
         pub origin: Point,
         lower_left_corner: Point,
@@ -92,9 +110,12 @@ mod camera {
         v: Vector,
         w: Vector,
         lens_radius: f64,
+        time0: f64,
+        time1: f64,
     }
 
     impl Camera {
+        #[allow(clippy::too_many_arguments)]
         pub fn new(
             lookfrom: Point,
             lookat: Point,
@@ -103,6 +124,8 @@ mod camera {
             aspect_ratio: f64,
             aperture: f64,
             focus_dist: f64,
+            time0: f64,
+            time1: f64,
         ) -> Self {
             let theta = f64::to_radians(vertical_fov);
             let h = (theta / 2.0).tan();
@@ -129,18 +152,24 @@ mod camera {
                 v,
                 w,
                 lens_radius,
+                time0,
+                time1,
             }
         }
 
-        pub fn get_ray(&self, s: f64, t: f64) -> Ray {
-            let rd = self.lens_radius * random_point_in_unit_disk();
+        pub fn get_ray(&self, s: f64, t: f64, rng: &mut dyn RngCore) -> Ray {
+            let rd = self.lens_radius * random_point_in_unit_disk(rng);
             let offset = self.u * rd.x + self.v * rd.y;
 
+            let distribution = Uniform::new(self.time0, self.time1);
+            let time = distribution.sample(rng);
+
             Ray::new(
                 self.origin + offset,
                 self.lower_left_corner + s * self.horizontal + t * self.vertical
                     - self.origin
                     - offset,
+                time,
             )
         }
     }
@@ -1,21 +1,37 @@
+use crate::texture::{SolidColor, Texture};
 use crate::util::Vector;
 use crate::{random_point_in_unit_sphere, random_unit_vector};
 use crate::{Color, HitRecord, Ray};
-use rand::Rng;
+use rand::{Rng, RngCore};
+use std::sync::Arc;
 
 pub trait Material {
     /// Returns the scattered ray and its attenuation
-    fn scatter(&self, input_ray: &Ray, hit_record: &HitRecord) -> Option<(Ray, Color)>;
+    fn scatter(
+        &self,
+        input_ray: &Ray,
+        hit_record: &HitRecord,
+        rng: &mut dyn RngCore,
+    ) -> Option<(Ray, Color)>;
+
+    /// Returns the light this material emits on its own; black for non-emissive materials
+    fn emitted(&self) -> Color {
+        Color::from_element(0.0)
+    }
 }
 
 pub struct Lambertian {
-    albedo: Color,
+    albedo: Arc<dyn Texture>,
 }
 
 impl Lambertian {
-    pub fn new(albedo: Color) -> Self {
+    pub fn new(albedo: Arc<dyn Texture>) -> Self {
         Self { albedo }
     }
+
+    pub fn from_color(albedo: Color) -> Self {
+        Self::new(Arc::new(SolidColor::new(albedo)))
+    }
 }
 
 fn vector_near_zero(vector: &Vector) -> bool {
@@ -25,8 +41,13 @@ fn vector_near_zero(vector: &Vector) -> bool {
 }
 
 impl Material for Lambertian {
-    fn scatter(&self, _input_ray: &Ray, hit_record: &HitRecord) -> Option<(Ray, Color)> {
-        let mut scatter_direction = hit_record.normal + random_unit_vector();
+    fn scatter(
+        &self,
+        input_ray: &Ray,
+        hit_record: &HitRecord,
+        rng: &mut dyn RngCore,
+    ) -> Option<(Ray, Color)> {
+        let mut scatter_direction = hit_record.normal + random_unit_vector(rng);
 
         // handle case where random_unit_vector is very close to -hit_record.normal
         // i.e. scatter_direction is very close to zero
@@ -34,9 +55,12 @@ impl Material for Lambertian {
             scatter_direction = hit_record.normal;
         };
 
-        let scattered_ray = Ray::new(hit_record.point, scatter_direction);
+        let scattered_ray = Ray::new(hit_record.point, scatter_direction, input_ray.time);
+        let attenuation = self
+            .albedo
+            .value(hit_record.u, hit_record.v, &hit_record.point);
 
-        Some((scattered_ray, self.albedo))
+        Some((scattered_ray, attenuation))
     }
 }
 
@@ -59,12 +83,18 @@ fn reflect(vector: &Vector, normal: &Vector) -> Vector {
 }
 
 impl Material for Metal {
-    fn scatter(&self, input_ray: &Ray, hit_record: &HitRecord) -> Option<(Ray, Color)> {
+    fn scatter(
+        &self,
+        input_ray: &Ray,
+        hit_record: &HitRecord,
+        rng: &mut dyn RngCore,
+    ) -> Option<(Ray, Color)> {
         let reflected_ray = reflect(&input_ray.direction.normalize(), &hit_record.normal);
 
         let scattered_ray = Ray::new(
             hit_record.point,
-            reflected_ray + self.fuzz * random_point_in_unit_sphere(),
+            reflected_ray + self.fuzz * random_point_in_unit_sphere(rng),
+            input_ray.time,
         );
 
         if scattered_ray.direction.dot(&hit_record.normal) > 0.0 {
@@ -101,7 +131,12 @@ impl Dielectric {
 }
 
 impl Material for Dielectric {
-    fn scatter(&self, input_ray: &Ray, hit_record: &HitRecord) -> Option<(Ray, Color)> {
+    fn scatter(
+        &self,
+        input_ray: &Ray,
+        hit_record: &HitRecord,
+        rng: &mut dyn RngCore,
+    ) -> Option<(Ray, Color)> {
         let refraction_ratio = ternary!(
             hit_record.front_face,
             1.0 / self.refraction_index,
@@ -113,8 +148,8 @@ impl Material for Dielectric {
         let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
 
         let cannot_refract = (refraction_ratio * sin_theta) > 1.0;
-        let has_reflectance = Dielectric::reflectance(cos_theta, refraction_ratio)
-            > rand::thread_rng().gen_range(0.0..1.0);
+        let has_reflectance =
+            Dielectric::reflectance(cos_theta, refraction_ratio) > rng.gen_range(0.0..1.0);
 
         let scatter_direction = ternary!(
             cannot_refract || has_reflectance,
@@ -122,9 +157,34 @@ impl Material for Dielectric {
             refract(&unit_direction, &hit_record.normal, refraction_ratio)
         );
 
-        let scattered_ray = Ray::new(hit_record.point, scatter_direction);
+        let scattered_ray = Ray::new(hit_record.point, scatter_direction, input_ray.time);
         let attenuation = Color::from_element(1.0);
 
         Some((scattered_ray, attenuation))
     }
 }
+
+pub struct DiffuseLight {
+    emit: Color,
+}
+
+impl DiffuseLight {
+    pub fn new(emit: Color) -> Self {
+        Self { emit }
+    }
+}
+
+impl Material for DiffuseLight {
+    fn scatter(
+        &self,
+        _input_ray: &Ray,
+        _hit_record: &HitRecord,
+        _rng: &mut dyn RngCore,
+    ) -> Option<(Ray, Color)> {
+        None
+    }
+
+    fn emitted(&self) -> Color {
+        self.emit
+    }
+}
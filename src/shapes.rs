@@ -1,16 +1,27 @@
-use crate::util::Point;
-use crate::{HitRecord, Hittable, Material, Ray};
+use crate::aabb::Aabb;
+use crate::util::{Point, Vector};
+use crate::{HitRecord, Hittable, HittableList, Material, Ray};
 
-use std::rc::Rc;
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+/// Spherical (u, v) surface coordinates of a point on a unit sphere, derived from the
+/// angles of its outward normal.
+fn sphere_uv(outward_normal: &Vector) -> (f64, f64) {
+    let u = (f64::atan2(-outward_normal.z, outward_normal.x) + PI) / (2.0 * PI);
+    let v = f64::acos(-outward_normal.y) / PI;
+
+    (u, v)
+}
 
 pub struct Sphere {
     pub origin: Point,
     pub radius: f64,
-    pub material: Rc<dyn Material>,
+    pub material: Arc<dyn Material>,
 }
 
 impl Sphere {
-    pub fn new(origin: Point, radius: f64, material: Rc<dyn Material>) -> Self {
+    pub fn new(origin: Point, radius: f64, material: Arc<dyn Material>) -> Self {
         Self {
             origin,
             radius,
@@ -19,33 +30,312 @@ impl Sphere {
     }
 }
 
+fn sphere_hit(
+    center: Point,
+    radius: f64,
+    material: &Arc<dyn Material>,
+    ray: &Ray,
+    t_min: f64,
+    t_max: f64,
+) -> Option<HitRecord> {
+    let oc = ray.origin - center;
+
+    let a = ray.direction.norm_squared();
+    let half_b = oc.dot(&ray.direction);
+    let c = oc.norm_squared() - radius * radius;
+
+    let discriminant = half_b * half_b - a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let mut root = (-half_b - discriminant.sqrt()) / a;
+
+    if root < t_min || t_max < root {
+        root = (-half_b + discriminant.sqrt()) / a;
+        if root < t_min || t_max < root {
+            return None;
+        }
+    }
+
+    let point = ray.at(root);
+    let outward_normal = (point - center) / radius;
+    let (u, v) = sphere_uv(&outward_normal);
+
+    let material = Arc::clone(material);
+
+    Some(HitRecord::new(
+        point,
+        &outward_normal,
+        material,
+        root,
+        ray,
+        u,
+        v,
+    ))
+}
+
 impl Hittable for Sphere {
     fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
-        let oc = ray.origin - self.origin;
+        sphere_hit(self.origin, self.radius, &self.material, ray, t_min, t_max)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let radius = Vector::from_element(self.radius);
+
+        Some(Aabb::new(self.origin - radius, self.origin + radius))
+    }
+}
+
+pub struct MovingSphere {
+    pub center0: Point,
+    pub center1: Point,
+    pub time0: f64,
+    pub time1: f64,
+    pub radius: f64,
+    pub material: Arc<dyn Material>,
+}
+
+impl MovingSphere {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        center0: Point,
+        center1: Point,
+        time0: f64,
+        time1: f64,
+        radius: f64,
+        material: Arc<dyn Material>,
+    ) -> Self {
+        Self {
+            center0,
+            center1,
+            time0,
+            time1,
+            radius,
+            material,
+        }
+    }
+
+    pub fn center(&self, time: f64) -> Point {
+        self.center0
+            + ((time - self.time0) / (self.time1 - self.time0)) * (self.center1 - self.center0)
+    }
+}
+
+impl Hittable for MovingSphere {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        sphere_hit(
+            self.center(ray.time),
+            self.radius,
+            &self.material,
+            ray,
+            t_min,
+            t_max,
+        )
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let radius = Vector::from_element(self.radius);
+
+        let box0 = Aabb::new(
+            self.center(self.time0) - radius,
+            self.center(self.time0) + radius,
+        );
+        let box1 = Aabb::new(
+            self.center(self.time1) - radius,
+            self.center(self.time1) + radius,
+        );
+
+        Some(Aabb::surrounding_box(&box0, &box1))
+    }
+}
 
-        let a = ray.direction.norm_squared();
-        let half_b = oc.dot(&ray.direction);
-        let c = oc.norm_squared() - self.radius * self.radius;
+/// Selects which two axes a `Rect` lies in; the remaining axis is fixed at `k`.
+#[derive(Clone, Copy)]
+pub enum Plane {
+    XY,
+    XZ,
+    YZ,
+}
+
+impl Plane {
+    /// Returns `(fixed_axis, a_axis, b_axis)`.
+    fn axes(self) -> (usize, usize, usize) {
+        match self {
+            Plane::XY => (2, 0, 1),
+            Plane::XZ => (1, 0, 2),
+            Plane::YZ => (0, 1, 2),
+        }
+    }
+}
 
-        let discriminant = half_b * half_b - a * c;
-        if discriminant < 0.0 {
+pub struct Rect {
+    pub plane: Plane,
+    pub a0: f64,
+    pub a1: f64,
+    pub b0: f64,
+    pub b1: f64,
+    pub k: f64,
+    pub material: Arc<dyn Material>,
+}
+
+impl Rect {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        plane: Plane,
+        a0: f64,
+        a1: f64,
+        b0: f64,
+        b1: f64,
+        k: f64,
+        material: Arc<dyn Material>,
+    ) -> Self {
+        Self {
+            plane,
+            a0,
+            a1,
+            b0,
+            b1,
+            k,
+            material,
+        }
+    }
+}
+
+impl Hittable for Rect {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        let (fixed_axis, a_axis, b_axis) = self.plane.axes();
+
+        let t = (self.k - ray.origin[fixed_axis]) / ray.direction[fixed_axis];
+        if t < t_min || t_max < t {
             return None;
         }
 
-        let mut root = (-half_b - discriminant.sqrt()) / a;
+        let a = ray.origin[a_axis] + t * ray.direction[a_axis];
+        let b = ray.origin[b_axis] + t * ray.direction[b_axis];
 
-        if root < t_min || t_max < root {
-            root = (-half_b + discriminant.sqrt()) / a;
-            if root < t_min || t_max < root {
-                return None;
-            }
+        if a < self.a0 || self.a1 < a || b < self.b0 || self.b1 < b {
+            return None;
         }
 
-        let point = ray.at(root);
-        let outward_normal = (point - self.origin) / self.radius;
+        let point = ray.at(t);
+
+        let mut outward_normal = Vector::from_element(0.0);
+        outward_normal[fixed_axis] = 1.0;
+
+        let u = (a - self.a0) / (self.a1 - self.a0);
+        let v = (b - self.b0) / (self.b1 - self.b0);
+
+        let material = Arc::clone(&self.material);
+
+        Some(HitRecord::new(
+            point,
+            &outward_normal,
+            material,
+            t,
+            ray,
+            u,
+            v,
+        ))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let (fixed_axis, a_axis, b_axis) = self.plane.axes();
+
+        // pad the fixed axis slightly so the box has nonzero width in every dimension
+        let padding = 0.0001;
+
+        let mut min = Point::from_element(0.0);
+        let mut max = Point::from_element(0.0);
+
+        min[fixed_axis] = self.k - padding;
+        max[fixed_axis] = self.k + padding;
+        min[a_axis] = self.a0;
+        max[a_axis] = self.a1;
+        min[b_axis] = self.b0;
+        max[b_axis] = self.b1;
+
+        Some(Aabb::new(min, max))
+    }
+}
+
+pub struct Cuboid {
+    min: Point,
+    max: Point,
+    sides: HittableList,
+}
 
-        let material = Rc::clone(&self.material);
+impl Cuboid {
+    pub fn new(min: Point, max: Point, material: Arc<dyn Material>) -> Self {
+        let mut sides = HittableList::with_capacity(6);
+
+        sides.add(Arc::new(Rect::new(
+            Plane::XY,
+            min.x,
+            max.x,
+            min.y,
+            max.y,
+            max.z,
+            Arc::clone(&material),
+        )));
+        sides.add(Arc::new(Rect::new(
+            Plane::XY,
+            min.x,
+            max.x,
+            min.y,
+            max.y,
+            min.z,
+            Arc::clone(&material),
+        )));
+
+        sides.add(Arc::new(Rect::new(
+            Plane::XZ,
+            min.x,
+            max.x,
+            min.z,
+            max.z,
+            max.y,
+            Arc::clone(&material),
+        )));
+        sides.add(Arc::new(Rect::new(
+            Plane::XZ,
+            min.x,
+            max.x,
+            min.z,
+            max.z,
+            min.y,
+            Arc::clone(&material),
+        )));
+
+        sides.add(Arc::new(Rect::new(
+            Plane::YZ,
+            min.y,
+            max.y,
+            min.z,
+            max.z,
+            max.x,
+            Arc::clone(&material),
+        )));
+        sides.add(Arc::new(Rect::new(
+            Plane::YZ,
+            min.y,
+            max.y,
+            min.z,
+            max.z,
+            min.x,
+            material,
+        )));
+
+        Self { min, max, sides }
+    }
+}
+
+impl Hittable for Cuboid {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+        self.sides.hit(ray, t_min, t_max)
+    }
 
-        Some(HitRecord::new(point, &outward_normal, material, root, ray))
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(Aabb::new(self.min, self.max))
     }
 }
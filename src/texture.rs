@@ -0,0 +1,50 @@
+use crate::util::Point;
+use crate::Color;
+
+use std::sync::Arc;
+
+pub trait Texture: Sync + Send {
+    fn value(&self, u: f64, v: f64, point: &Point) -> Color;
+}
+
+pub struct SolidColor {
+    color: Color,
+}
+
+impl SolidColor {
+    pub fn new(color: Color) -> Self {
+        Self { color }
+    }
+}
+
+impl Texture for SolidColor {
+    fn value(&self, _u: f64, _v: f64, _point: &Point) -> Color {
+        self.color
+    }
+}
+
+pub struct CheckerTexture {
+    odd: Arc<dyn Texture>,
+    even: Arc<dyn Texture>,
+    scale: f64,
+}
+
+impl CheckerTexture {
+    pub fn new(odd: Arc<dyn Texture>, even: Arc<dyn Texture>, scale: f64) -> Self {
+        Self { odd, even, scale }
+    }
+}
+
+impl Texture for CheckerTexture {
+    fn value(&self, u: f64, v: f64, point: &Point) -> Color {
+        let sign = (self.scale * point.x).sin()
+            * (self.scale * point.y).sin()
+            * (self.scale * point.z).sin();
+
+        if sign < 0.0 {
+            self.odd.value(u, v, point)
+        } else {
+            self.even.value(u, v, point)
+        }
+    }
+}